@@ -0,0 +1,135 @@
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
+
+pub const DEFAULT_DH_CACHE_SIZE: usize = 100;
+
+type DhCacheKey = (String, String, u64);
+
+/// Fingerprints the key material actually used to derive a shared secret, so that a cache entry
+/// keyed only on the `(my_did, their_did)` pair can't be handed back after either side rotates
+/// their keys. Doesn't need to be cryptographically strong -- it only has to change whenever the
+/// key material does, not resist deliberate collision.
+fn key_material_fingerprint(private_key: &[u8], public_key: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    private_key.hash(&mut hasher);
+    public_key.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct DhCacheInner {
+    secrets: HashMap<DhCacheKey, Vec<u8>>,
+    recency: VecDeque<DhCacheKey>
+}
+
+/// Caches precomputed Diffie-Hellman shared secrets keyed by `(my_did, their_did, key_material_fingerprint)`
+/// so repeated `encrypt`/`decrypt` calls between the same pair of parties skip re-deriving the shared
+/// secret, while a key rotation on either side (which changes the fingerprint) naturally misses the
+/// cache instead of returning a stale secret. Bounded to `max_entries`, evicting the least recently
+/// used entry on overflow.
+pub struct DhCache {
+    max_entries: usize,
+    inner: RwLock<DhCacheInner>
+}
+
+impl DhCache {
+    pub fn new(max_entries: usize) -> DhCache {
+        DhCache {
+            max_entries: max_entries,
+            inner: RwLock::new(DhCacheInner {
+                secrets: HashMap::new(),
+                recency: VecDeque::new()
+            })
+        }
+    }
+
+    pub fn get(&self, my_did: &str, their_did: &str, private_key: &[u8], public_key: &[u8]) -> Option<Vec<u8>> {
+        let key = (my_did.to_string(), their_did.to_string(), key_material_fingerprint(private_key, public_key));
+        let mut inner = self.inner.write().unwrap();
+
+        let secret = inner.secrets.get(&key).cloned();
+        if secret.is_some() {
+            inner.recency.retain(|k| k != &key);
+            inner.recency.push_back(key);
+        }
+        secret
+    }
+
+    pub fn put(&self, my_did: &str, their_did: &str, private_key: &[u8], public_key: &[u8], shared_secret: Vec<u8>) {
+        let key = (my_did.to_string(), their_did.to_string(), key_material_fingerprint(private_key, public_key));
+        let mut inner = self.inner.write().unwrap();
+
+        if !inner.secrets.contains_key(&key) && inner.secrets.len() >= self.max_entries {
+            if let Some(lru_key) = inner.recency.pop_front() {
+                inner.secrets.remove(&lru_key);
+            }
+        }
+
+        inner.recency.retain(|k| k != &key);
+        inner.recency.push_back(key.clone());
+        inner.secrets.insert(key, shared_secret);
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap().secrets.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_returns_none_for_missing_entry() {
+        let cache = DhCache::new(DEFAULT_DH_CACHE_SIZE);
+        assert_eq!(None, cache.get("a", "b", b"priv", b"pub"));
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let cache = DhCache::new(DEFAULT_DH_CACHE_SIZE);
+        cache.put("a", "b", b"priv", b"pub", vec![1, 2, 3]);
+        assert_eq!(Some(vec![1, 2, 3]), cache.get("a", "b", b"priv", b"pub"));
+    }
+
+    #[test]
+    fn get_misses_after_either_sides_key_material_changes() {
+        let cache = DhCache::new(DEFAULT_DH_CACHE_SIZE);
+        cache.put("a", "b", b"priv1", b"pub1", vec![1, 2, 3]);
+
+        // A fresh lookup with rotated key material for either side must not return the secret
+        // derived under the old keys, even though the DID pair is unchanged.
+        assert_eq!(None, cache.get("a", "b", b"priv2", b"pub1"));
+        assert_eq!(None, cache.get("a", "b", b"priv1", b"pub2"));
+        assert_eq!(Some(vec![1, 2, 3]), cache.get("a", "b", b"priv1", b"pub1"));
+    }
+
+    #[test]
+    fn eviction_bounds_memory_to_max_entries() {
+        let cache = DhCache::new(2);
+        cache.put("a", "1", b"priv", b"pub", vec![1]);
+        cache.put("a", "2", b"priv", b"pub", vec![2]);
+        cache.put("a", "3", b"priv", b"pub", vec![3]);
+
+        assert_eq!(2, cache.len());
+        assert_eq!(None, cache.get("a", "1", b"priv", b"pub"));
+        assert_eq!(Some(vec![2]), cache.get("a", "2", b"priv", b"pub"));
+        assert_eq!(Some(vec![3]), cache.get("a", "3", b"priv", b"pub"));
+    }
+
+    #[test]
+    fn get_refreshes_recency_so_it_survives_eviction() {
+        let cache = DhCache::new(2);
+        cache.put("a", "1", b"priv", b"pub", vec![1]);
+        cache.put("a", "2", b"priv", b"pub", vec![2]);
+
+        // touch "1" so "2" becomes the least recently used entry
+        cache.get("a", "1", b"priv", b"pub");
+        cache.put("a", "3", b"priv", b"pub", vec![3]);
+
+        assert_eq!(Some(vec![1]), cache.get("a", "1", b"priv", b"pub"));
+        assert_eq!(None, cache.get("a", "2", b"priv", b"pub"));
+        assert_eq!(Some(vec![3]), cache.get("a", "3", b"priv", b"pub"));
+    }
+}
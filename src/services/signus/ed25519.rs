@@ -0,0 +1,233 @@
+extern crate sodiumoxide;
+extern crate libsodium_sys;
+
+use services::signus::CryptoType;
+use errors::crypto::CryptoError;
+
+use self::sodiumoxide::crypto::box_;
+use self::sodiumoxide::crypto::generichash;
+use self::sodiumoxide::crypto::sign;
+
+pub struct ED25519Signus {}
+
+impl ED25519Signus {
+    pub fn new() -> ED25519Signus {
+        ED25519Signus {}
+    }
+
+    fn verkey_to_box_pk(verkey: &sign::PublicKey) -> box_::PublicKey {
+        let mut buf = [0u8; box_::PUBLICKEYBYTES];
+        unsafe {
+            libsodium_sys::crypto_sign_ed25519_pk_to_curve25519(buf.as_mut_ptr(), verkey.0.as_ptr());
+        }
+        box_::PublicKey(buf)
+    }
+
+    fn signkey_to_box_sk(signkey: &sign::SecretKey) -> box_::SecretKey {
+        let mut buf = [0u8; box_::SECRETKEYBYTES];
+        unsafe {
+            libsodium_sys::crypto_sign_ed25519_sk_to_curve25519(buf.as_mut_ptr(), signkey.0.as_ptr());
+        }
+        box_::SecretKey(buf)
+    }
+
+    /// Derives the box nonce from `blake2b(ephemeral_pk || recipient_pk)`, as libsodium's sealed
+    /// box construction does, so both sides recompute the same nonce without transmitting one.
+    fn seal_nonce(ephemeral_pk: &box_::PublicKey, recipient_pk: &box_::PublicKey) -> box_::Nonce {
+        let mut state = generichash::State::new(Some(box_::NONCEBYTES), None).unwrap();
+        state.update(&ephemeral_pk.0).unwrap();
+        state.update(&recipient_pk.0).unwrap();
+        let digest = state.finalize().unwrap();
+        box_::Nonce::from_slice(digest.as_ref()).unwrap()
+    }
+}
+
+impl CryptoType for ED25519Signus {
+    fn create_key_pair(&self) -> (Vec<u8>, Vec<u8>) {
+        let (pk, sk) = box_::gen_keypair();
+        (pk[..].to_vec(), sk[..].to_vec())
+    }
+
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sk = box_::SecretKey::from_slice(private_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 secret key".to_string()))?;
+        let pk = box_::PublicKey::from_slice(public_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 public key".to_string()))?;
+        let nonce = box_::Nonce::from_slice(nonce)
+            .ok_or(CryptoError::InvalidStructure("Invalid nonce".to_string()))?;
+        Ok(box_::seal(doc, &nonce, &pk, &sk))
+    }
+
+    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sk = box_::SecretKey::from_slice(private_key).unwrap();
+        let pk = box_::PublicKey::from_slice(public_key).unwrap();
+        let nonce = box_::Nonce::from_slice(nonce).unwrap();
+        box_::open(doc, &nonce, &pk, &sk).map_err(|_| CryptoError::BackendError("Unable to open box".to_string()))
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        box_::gen_nonce()[..].to_vec()
+    }
+
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> (Vec<u8>, Vec<u8>) {
+        let (vk, sk) = match seed {
+            Some(seed) => {
+                let seed = sign::Seed::from_slice(seed).unwrap();
+                sign::keypair_from_seed(&seed)
+            }
+            None => sign::gen_keypair()
+        };
+        (vk[..].to_vec(), sk[..].to_vec())
+    }
+
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sk = sign::SecretKey::from_slice(private_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 sign key".to_string()))?;
+        Ok(sign::sign_detached(doc, &sk)[..].to_vec())
+    }
+
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> bool {
+        let vk = match sign::PublicKey::from_slice(public_key) {
+            Some(vk) => vk,
+            None => return false
+        };
+        let sig = match sign::Signature::from_slice(signature) {
+            Some(sig) => sig,
+            None => return false
+        };
+        sign::verify_detached(&sig, doc, &vk)
+    }
+
+    fn seal(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let recipient_vk = sign::PublicKey::from_slice(public_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 verkey".to_string()))?;
+        let recipient_pk = ED25519Signus::verkey_to_box_pk(&recipient_vk);
+
+        let (ephemeral_pk, ephemeral_sk) = box_::gen_keypair();
+        let nonce = ED25519Signus::seal_nonce(&ephemeral_pk, &recipient_pk);
+
+        let ciphertext = box_::seal(doc, &nonce, &recipient_pk, &ephemeral_sk);
+
+        let mut sealed = ephemeral_pk[..].to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn seal_open(&self, private_key: &[u8], public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let my_sign_key = sign::SecretKey::from_slice(private_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 sign key".to_string()))?;
+        let my_verkey = sign::PublicKey::from_slice(public_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 verkey".to_string()))?;
+
+        let my_box_sk = ED25519Signus::signkey_to_box_sk(&my_sign_key);
+        let my_box_pk = ED25519Signus::verkey_to_box_pk(&my_verkey);
+
+        if doc.len() < box_::PUBLICKEYBYTES {
+            return Err(CryptoError::InvalidStructure("Sealed message is too short".to_string()));
+        }
+
+        let (ephemeral_pk, ciphertext) = doc.split_at(box_::PUBLICKEYBYTES);
+        let ephemeral_pk = box_::PublicKey::from_slice(ephemeral_pk).unwrap();
+        let nonce = ED25519Signus::seal_nonce(&ephemeral_pk, &my_box_pk);
+
+        box_::open(ciphertext, &nonce, &ephemeral_pk, &my_box_sk)
+            .map_err(|_| CryptoError::BackendError("Unable to open sealed box".to_string()))
+    }
+
+    fn precompute(&self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sk = box_::SecretKey::from_slice(private_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 secret key".to_string()))?;
+        let pk = box_::PublicKey::from_slice(public_key)
+            .ok_or(CryptoError::InvalidStructure("Invalid ed25519 public key".to_string()))?;
+        Ok(box_::precompute(&pk, &sk)[..].to_vec())
+    }
+
+    fn encrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Vec<u8> {
+        let key = box_::PrecomputedKey::from_slice(shared_secret).unwrap();
+        let nonce = box_::Nonce::from_slice(nonce).unwrap();
+        box_::seal_precomputed(doc, &nonce, &key)
+    }
+
+    fn decrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let key = box_::PrecomputedKey::from_slice(shared_secret).unwrap();
+        let nonce = box_::Nonce::from_slice(nonce).unwrap();
+        box_::open_precomputed(doc, &nonce, &key)
+            .map_err(|_| CryptoError::BackendError("Unable to open box".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ed25519_sign_verify_works() {
+        let signus = ED25519Signus::new();
+        let (vk, sk) = signus.create_key_pair_for_signature(None);
+        let doc = b"some message";
+
+        let signature = signus.sign(&sk, doc).unwrap();
+        assert!(signus.verify(&vk, doc, &signature));
+    }
+
+    #[test]
+    fn ed25519_encrypt_decrypt_works() {
+        let signus = ED25519Signus::new();
+        let (pk1, sk1) = signus.create_key_pair();
+        let (pk2, sk2) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let encrypted = signus.encrypt(&sk1, &pk2, doc, &nonce).unwrap();
+        let decrypted = signus.decrypt(&sk2, &pk1, &encrypted, &nonce).unwrap();
+        assert_eq!(doc.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn ed25519_seal_seal_open_works() {
+        let signus = ED25519Signus::new();
+        let (verkey, sign_key) = signus.create_key_pair_for_signature(None);
+        let doc = b"some anonymous message";
+
+        let sealed = signus.seal(&verkey, doc).unwrap();
+        let opened = signus.seal_open(&sign_key, &verkey, &sealed).unwrap();
+
+        assert_eq!(doc.to_vec(), opened);
+    }
+
+    #[test]
+    fn ed25519_precomputed_encrypt_is_compatible_with_plain_decrypt() {
+        let signus = ED25519Signus::new();
+        let (pk1, sk1) = signus.create_key_pair();
+        let (pk2, sk2) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let shared_secret = signus.precompute(&sk1, &pk2).unwrap();
+        let encrypted = signus.encrypt_precomputed(&shared_secret, doc, &nonce);
+        let decrypted = signus.decrypt(&sk2, &pk1, &encrypted, &nonce).unwrap();
+
+        assert_eq!(doc.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn ed25519_seal_open_fails_for_wrong_recipient() {
+        let signus = ED25519Signus::new();
+        let (verkey, _) = signus.create_key_pair_for_signature(None);
+        let (_, other_sign_key) = signus.create_key_pair_for_signature(None);
+        let doc = b"some anonymous message";
+
+        let sealed = signus.seal(&verkey, doc).unwrap();
+
+        assert!(signus.seal_open(&other_sign_key, &verkey, &sealed).is_err());
+    }
+
+    #[test]
+    fn ed25519_seal_returns_err_instead_of_panicking_for_invalid_recipient_verkey() {
+        let signus = ED25519Signus::new();
+        let doc = b"some anonymous message";
+
+        let bogus_verkey = vec![0u8; 31];
+        assert!(signus.seal(&bogus_verkey, doc).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use errors::crypto::CryptoError;
+use utils::crypto::base58::Base58;
+
+const MULTICODEC_ED25519_PUB: [u8; 2] = [0xed, 0x01];
+const MULTICODEC_P256_PUB: [u8; 2] = [0x80, 0x24];
+const MULTICODEC_SECP256K1_PUB: [u8; 2] = [0xe7, 0x01];
+
+fn prefix_for(crypto_type: &str) -> Option<[u8; 2]> {
+    match crypto_type {
+        "ed25519" => Some(MULTICODEC_ED25519_PUB),
+        "p256" => Some(MULTICODEC_P256_PUB),
+        "secp256k1" => Some(MULTICODEC_SECP256K1_PUB),
+        _ => None
+    }
+}
+
+fn crypto_type_for(prefix: [u8; 2]) -> Option<&'static str> {
+    match prefix {
+        MULTICODEC_ED25519_PUB => Some("ed25519"),
+        MULTICODEC_P256_PUB => Some("p256"),
+        MULTICODEC_SECP256K1_PUB => Some("secp256k1"),
+        _ => None
+    }
+}
+
+/// Expected raw verkey length once a multicodec prefix has been stripped, used to tell a
+/// genuinely prefixed key apart from a legacy unprefixed key that happens to start with the same
+/// 2 bytes by chance.
+fn expected_verkey_len_for(crypto_type: &str) -> usize {
+    match crypto_type {
+        "ed25519" => 32,
+        "p256" => 65,
+        "secp256k1" => 65,
+        _ => unreachable!("expected_verkey_len_for called with an unrecognised crypto type")
+    }
+}
+
+/// Base58-encodes `raw_key`, prepending the 2-byte multicodec tag for `crypto_type` when one is
+/// known. Keys for an unrecognised crypto type are encoded unprefixed, matching legacy behaviour.
+pub fn encode_key(crypto_type: &str, raw_key: &[u8]) -> String {
+    match prefix_for(crypto_type) {
+        Some(prefix) => {
+            let mut tagged = Vec::with_capacity(2 + raw_key.len());
+            tagged.extend_from_slice(&prefix);
+            tagged.extend_from_slice(raw_key);
+            Base58::encode(&tagged)
+        }
+        None => Base58::encode(raw_key)
+    }
+}
+
+/// Base58-decodes `key`, stripping a recognised multicodec prefix and returning the crypto type
+/// it names alongside the raw key bytes. Returns `(None, bytes)` for legacy unprefixed keys.
+///
+/// A sniffed prefix is only trusted when the remaining bytes also match that crypto type's
+/// expected verkey length; otherwise it's treated as a coincidental match against a legacy
+/// unprefixed key (e.g. a 32-byte ed25519 verkey that happens to start with `0xed, 0x01`) and the
+/// key is returned unprefixed, as if no multicodec tag had been recognised.
+pub fn decode_key(key: &str) -> Result<(Option<String>, Vec<u8>), CryptoError> {
+    let bytes = Base58::decode(key)?;
+
+    if bytes.len() < 2 {
+        return Ok((None, bytes));
+    }
+
+    match crypto_type_for([bytes[0], bytes[1]]) {
+        Some(crypto_type) if bytes.len() - 2 == expected_verkey_len_for(crypto_type) =>
+            Ok((Some(crypto_type.to_string()), bytes[2..].to_vec())),
+        _ => Ok((None, bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_round_trip_works_for_each_curve() {
+        for crypto_type in &["ed25519", "p256", "secp256k1"] {
+            let raw_key = vec![1u8; expected_verkey_len_for(crypto_type)];
+            let encoded = encode_key(crypto_type, &raw_key);
+            let (decoded_type, decoded_key) = decode_key(&encoded).unwrap();
+
+            assert_eq!(Some(crypto_type.to_string()), decoded_type);
+            assert_eq!(raw_key, decoded_key);
+        }
+    }
+
+    #[test]
+    fn decode_key_falls_back_for_legacy_unprefixed_key() {
+        let raw_key = vec![9u8; 32];
+        let encoded = Base58::encode(&raw_key);
+
+        let (decoded_type, decoded_key) = decode_key(&encoded).unwrap();
+
+        assert_eq!(None, decoded_type);
+        assert_eq!(raw_key, decoded_key);
+    }
+
+    #[test]
+    fn decode_key_distinguishes_mixed_prefixes() {
+        let ed_key = encode_key("ed25519", &[1u8; 32]);
+        let p256_key = encode_key("p256", &[2u8; 65]);
+        let secp_key = encode_key("secp256k1", &[3u8; 65]);
+
+        assert_eq!(Some("ed25519".to_string()), decode_key(&ed_key).unwrap().0);
+        assert_eq!(Some("p256".to_string()), decode_key(&p256_key).unwrap().0);
+        assert_eq!(Some("secp256k1".to_string()), decode_key(&secp_key).unwrap().0);
+    }
+
+    #[test]
+    fn decode_key_falls_back_when_legacy_key_collides_with_a_multicodec_prefix() {
+        // A 32-byte unprefixed ed25519 verkey that happens to start with the reserved
+        // `MULTICODEC_ED25519_PUB` tag must not be misidentified as a prefixed key: stripping the
+        // first 2 bytes would leave only 30 bytes, which doesn't match ed25519's 32-byte verkey
+        // length, so the collision guard should fall back to treating it as legacy/unprefixed.
+        let mut raw_key = vec![7u8; 32];
+        raw_key[0] = MULTICODEC_ED25519_PUB[0];
+        raw_key[1] = MULTICODEC_ED25519_PUB[1];
+        let encoded = Base58::encode(&raw_key);
+
+        let (decoded_type, decoded_key) = decode_key(&encoded).unwrap();
+
+        assert_eq!(None, decoded_type);
+        assert_eq!(raw_key, decoded_key);
+    }
+}
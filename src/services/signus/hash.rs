@@ -0,0 +1,72 @@
+extern crate sha2;
+
+use self::sha2::{Sha256, Sha512, Digest};
+
+/// Hash algorithm used by `sign_digest`/`verify_digest` to condense a document before signing it,
+/// so large payloads only ever pass a fixed-size digest through the signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha512
+}
+
+/// Preferred algorithm for new signatures; verification accepts any tagged algorithm below.
+pub const DEFAULT_HASH_ALGORITHM: HashAlgorithm = HashAlgorithm::Sha512;
+
+impl HashAlgorithm {
+    /// The 1-byte tag prefixed to digest signatures so `verify_digest` knows which hash to
+    /// recompute without the caller needing to pass it back in.
+    pub fn tag(&self) -> u8 {
+        match *self {
+            HashAlgorithm::Sha256 => 0x01,
+            HashAlgorithm::Sha512 => 0x02
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Option<HashAlgorithm> {
+        match tag {
+            0x01 => Some(HashAlgorithm::Sha256),
+            0x02 => Some(HashAlgorithm::Sha512),
+            _ => None
+        }
+    }
+
+    pub fn digest(&self, doc: &[u8]) -> Vec<u8> {
+        match *self {
+            HashAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(doc);
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                hasher.update(doc);
+                hasher.finalize().to_vec()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_round_trips_through_from_tag() {
+        for algorithm in &[HashAlgorithm::Sha256, HashAlgorithm::Sha512] {
+            assert_eq!(Some(*algorithm), HashAlgorithm::from_tag(algorithm.tag()));
+        }
+    }
+
+    #[test]
+    fn from_tag_rejects_unknown_tag() {
+        assert_eq!(None, HashAlgorithm::from_tag(0xff));
+    }
+
+    #[test]
+    fn sha256_and_sha512_digests_differ_in_length() {
+        let doc = b"some document";
+        assert_eq!(32, HashAlgorithm::Sha256.digest(doc).len());
+        assert_eq!(64, HashAlgorithm::Sha512.digest(doc).len());
+    }
+}
@@ -1,11 +1,30 @@
 mod ed25519;
+mod p256;
+mod secp256k1;
+#[cfg(feature = "enable-crypto-none")]
+mod none;
+mod encoding;
+mod dh_cache;
+mod hash;
 pub mod types;
 
 use self::ed25519::ED25519Signus;
+use self::p256::P256Signus;
+use self::secp256k1::Secp256k1Signus;
+#[cfg(feature = "enable-crypto-none")]
+use self::none::NoneSignus;
+use self::encoding::{encode_key, decode_key};
+use self::dh_cache::{DhCache, DEFAULT_DH_CACHE_SIZE};
+use self::hash::{HashAlgorithm, DEFAULT_HASH_ALGORITHM};
 use self::types::{
     MyDidInfo,
     MyDid,
-    TheirDid
+    TheirDid,
+    VerKey,
+    SignKey,
+    Signature,
+    PublicKey,
+    SecretKey
 };
 use utils::crypto::base58::Base58;
 
@@ -15,27 +34,57 @@ use std::collections::HashMap;
 
 const DEFAULT_CRYPTO_TYPE: &'static str = "ed25519";
 
+/// `ed25519::create_key_pair_for_signature` derives the keypair directly from the seed bytes
+/// (`sign::Seed::from_slice`) rather than stretching it through a KDF like p256/secp256k1 do, so
+/// it requires an exact-length seed.
+const ED25519_SEED_LEN: usize = 32;
+
 trait CryptoType {
     fn create_key_pair(&self) -> (Vec<u8>, Vec<u8>);
-    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Vec<u8>;
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError>;
     fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError>;
     fn gen_nonce(&self) -> Vec<u8>;
     fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> (Vec<u8>, Vec<u8>);
-    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Vec<u8>;
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError>;
     fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> bool;
+    fn seal(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError>;
+    fn seal_open(&self, private_key: &[u8], public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError>;
+    fn precompute(&self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError>;
+    fn encrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Vec<u8>;
+    fn decrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError>;
+
+    /// Signs an already-hashed digest instead of the raw document. The default just defers to
+    /// `sign`, which is safe here since the digest is already a fixed-size, opaque byte string.
+    fn sign_digest(&self, private_key: &[u8], digest: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.sign(private_key, digest)
+    }
+
+    fn verify_digest(&self, public_key: &[u8], digest: &[u8], signature: &[u8]) -> bool {
+        self.verify(public_key, digest, signature)
+    }
 }
 
 pub struct SignusService {
-    crypto_types: HashMap<&'static str, Box<CryptoType>>
+    crypto_types: HashMap<&'static str, Box<CryptoType>>,
+    dh_cache: DhCache
 }
 
 impl SignusService {
     pub fn new() -> SignusService {
+        SignusService::with_dh_cache_size(DEFAULT_DH_CACHE_SIZE)
+    }
+
+    pub fn with_dh_cache_size(max_dh_cache_entries: usize) -> SignusService {
         let mut crypto_types: HashMap<&str, Box<CryptoType>> = HashMap::new();
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519Signus::new()));
+        crypto_types.insert("secp256k1", Box::new(Secp256k1Signus::new()));
+        crypto_types.insert("p256", Box::new(P256Signus::new()));
+        #[cfg(feature = "enable-crypto-none")]
+        crypto_types.insert("none", Box::new(NoneSignus::new()));
 
         SignusService {
-            crypto_types: crypto_types
+            crypto_types: crypto_types,
+            dh_cache: DhCache::new(max_dh_cache_entries)
         }
     }
 
@@ -49,16 +98,29 @@ impl SignusService {
         let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
 
         let seed = did_info.seed.as_ref().map(String::as_bytes);
+        if let Some(seed) = seed {
+            if xtype == "ed25519" && seed.len() != ED25519_SEED_LEN {
+                return Err(SignusError::CryptoError(CryptoError::InvalidStructure(
+                    format!("Invalid seed length for crypto type 'ed25519': expected {} bytes, got {}",
+                            ED25519_SEED_LEN, seed.len()))));
+            }
+        }
+
         let (public_key, secret_key) = signus.create_key_pair();
         let (ver_key, sign_key) = signus.create_key_pair_for_signature(seed);
         let did = did_info.did.as_ref().map(|did| Base58::decode(did)).unwrap_or(Ok(ver_key[0..16].to_vec()))?;
 
+        let encoded_ver_key = if did_info.self_describing_verkey.unwrap_or(false) {
+            encode_key(&xtype, &ver_key)
+        } else {
+            Base58::encode(&ver_key)
+        };
 
         let my_did = MyDid::new(Base58::encode(&did),
                                 xtype.clone(),
                                 Base58::encode(&public_key),
                                 Base58::encode(&secret_key),
-                                Base58::encode(&ver_key),
+                                encoded_ver_key,
                                 Base58::encode(&sign_key));
         println!("did {:?}", my_did.did);
 
@@ -72,26 +134,79 @@ impl SignusService {
 
         let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
 
-        let sign_key = Base58::decode(&my_did.sign_key)?;
-        let signature = signus.sign(&sign_key, doc.as_bytes());
+        let sign_key = SignKey::from_base58(&my_did.crypto_type, &my_did.sign_key)?;
+        let signature = signus.sign(sign_key.as_bytes(), doc.as_bytes())?;
         let signature = Base58::encode(&signature);
 
         Ok(signature)
     }
 
+    /// Hashes `doc` with `hash_algorithm` (SHA-512 if not given) and signs the digest instead of
+    /// the raw document, so large documents only ever pass a fixed-size digest to the signer.
+    /// The returned signature is prefixed with a 1-byte tag naming the hash algorithm used.
+    pub fn sign_digest(&self, my_did: &MyDid, doc: &str, hash_algorithm: Option<HashAlgorithm>) -> Result<String, SignusError> {
+        if !self.crypto_types.contains_key(&my_did.crypto_type.as_str()) {
+            return Err(SignusError::CryptoError(CryptoError::UnknownType(my_did.crypto_type.clone())));
+        }
+
+        let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
+        let hash_algorithm = hash_algorithm.unwrap_or(DEFAULT_HASH_ALGORITHM);
+
+        let sign_key = SignKey::from_base58(&my_did.crypto_type, &my_did.sign_key)?;
+        let digest = hash_algorithm.digest(doc.as_bytes());
+        let signature = signus.sign_digest(sign_key.as_bytes(), &digest)?;
+
+        let mut tagged_signature = vec![hash_algorithm.tag()];
+        tagged_signature.extend_from_slice(&signature);
+
+        Ok(Base58::encode(&tagged_signature))
+    }
+
     pub fn verify(&self, their_did: &TheirDid, doc: &str, signature: &str) -> Result<bool, SignusError> {
-        let xtype = their_did.crypto_type.clone().unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
+        let (decoded_type, raw_verkey) = decode_key(&their_did.verkey)?;
+        let xtype = decoded_type
+            .or_else(|| their_did.crypto_type.clone())
+            .unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
+
+        if !self.crypto_types.contains_key(&xtype.as_str()) {
+            return Err(SignusError::CryptoError(CryptoError::UnknownType(xtype)));
+        }
+
+        let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
+
+        let verkey = VerKey::from_bytes(&xtype, raw_verkey)?;
+        let signature = Signature::from_base58(&xtype, signature)?;
+
+        Ok(signus.verify(verkey.as_bytes(), &doc.as_bytes(), signature.as_bytes()))
+    }
+
+    /// Verifies a signature produced by `sign_digest`, recomputing the digest with the hash
+    /// algorithm named by the signature's leading tag byte.
+    pub fn verify_digest(&self, their_did: &TheirDid, doc: &str, signature: &str) -> Result<bool, SignusError> {
+        let (decoded_type, raw_verkey) = decode_key(&their_did.verkey)?;
+        let xtype = decoded_type
+            .or_else(|| their_did.crypto_type.clone())
+            .unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
 
         if !self.crypto_types.contains_key(&xtype.as_str()) {
             return Err(SignusError::CryptoError(CryptoError::UnknownType(xtype)));
         }
 
         let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
+        let verkey = VerKey::from_bytes(&xtype, raw_verkey)?;
 
-        let verkey = Base58::decode(&their_did.verkey)?;
-        let signature = Base58::decode(signature)?;
+        let tagged_signature = Base58::decode(signature)?;
+        if tagged_signature.is_empty() {
+            return Err(SignusError::CryptoError(CryptoError::InvalidStructure("Digest signature is empty".to_string())));
+        }
+
+        let (tag, raw_signature) = tagged_signature.split_at(1);
+        let hash_algorithm = HashAlgorithm::from_tag(tag[0])
+            .ok_or(SignusError::CryptoError(CryptoError::InvalidStructure(format!("Unknown hash algorithm tag {}", tag[0]))))?;
 
-        Ok(signus.verify(&verkey, &doc.as_bytes(), &signature))
+        let signature = Signature::from_bytes(&xtype, raw_signature.to_vec())?;
+        let digest = hash_algorithm.digest(doc.as_bytes());
+        Ok(signus.verify_digest(verkey.as_bytes(), &digest, signature.as_bytes()))
     }
 
     pub fn encrypt(&self, my_did: &MyDid, their_did: &TheirDid, doc: &str) -> Result<(String, String), SignusError> {
@@ -108,11 +223,12 @@ impl SignusService {
 
         let nonce = signus.gen_nonce();
 
-        let secret_key = Base58::decode(&my_did.secret_key)?;
-        let public_key = Base58::decode(&public_key)?;
+        let secret_key = SecretKey::from_base58(&my_did.crypto_type, &my_did.secret_key)?;
+        let public_key = PublicKey::from_base58(&my_did.crypto_type, &public_key)?;
         let doc = Base58::decode(&doc)?;
 
-        let encrypted_doc = signus.encrypt(&secret_key, &public_key, &doc, &nonce);
+        let shared_secret = self.dh_shared_secret(signus.as_ref(), &my_did.did, &their_did.did, secret_key.as_bytes(), public_key.as_bytes())?;
+        let encrypted_doc = signus.encrypt_precomputed(&shared_secret, &doc, &nonce);
         let encrypted_doc = Base58::encode(&encrypted_doc);
         let nonce = Base58::encode(&nonce);
         Ok((encrypted_doc, nonce))
@@ -130,15 +246,69 @@ impl SignusService {
         let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
         let public_key = their_did.pk.clone().unwrap();
 
-        let secret_key = Base58::decode(&my_did.secret_key)?;
-        let public_key = Base58::decode(&public_key)?;
+        let secret_key = SecretKey::from_base58(&my_did.crypto_type, &my_did.secret_key)?;
+        let public_key = PublicKey::from_base58(&my_did.crypto_type, &public_key)?;
         let doc = Base58::decode(&doc)?;
         let nonce = Base58::decode(&nonce)?;
 
-        let decrypted_doc = signus.decrypt(&secret_key, &public_key, &doc, &nonce)?;
+        let shared_secret = self.dh_shared_secret(signus.as_ref(), &my_did.did, &their_did.did, secret_key.as_bytes(), public_key.as_bytes())?;
+        let decrypted_doc = signus.decrypt_precomputed(&shared_secret, &doc, &nonce)?;
         let decrypted_doc = Base58::encode(&decrypted_doc);
         Ok(decrypted_doc)
     }
+
+    /// Returns the precomputed DH shared secret for `(my_did, their_did)`, deriving and caching
+    /// it on first use so later `encrypt`/`decrypt` calls between the same pair skip the DH step.
+    /// The cache is keyed on a fingerprint of `private_key`/`public_key` in addition to the DID
+    /// pair, so rotating either side's key naturally misses the cache instead of returning a
+    /// shared secret derived under the old keys.
+    fn dh_shared_secret(&self, signus: &CryptoType, my_did: &str, their_did: &str,
+                         private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if let Some(cached) = self.dh_cache.get(my_did, their_did, private_key, public_key) {
+            return Ok(cached);
+        }
+
+        let shared_secret = signus.precompute(private_key, public_key)?;
+        self.dh_cache.put(my_did, their_did, private_key, public_key, shared_secret.clone());
+        Ok(shared_secret)
+    }
+
+    /// Encrypts `doc` to `their_did`'s verkey without requiring a sender identity. The recipient
+    /// can open the result with only their own key pair; nobody else, not even the sender, can
+    /// later prove who sent it.
+    pub fn seal(&self, their_did: &TheirDid, doc: &str) -> Result<String, SignusError> {
+        let (decoded_type, raw_verkey) = decode_key(&their_did.verkey)?;
+        let xtype = decoded_type
+            .or_else(|| their_did.crypto_type.clone())
+            .unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
+
+        if !self.crypto_types.contains_key(&xtype.as_str()) {
+            return Err(SignusError::CryptoError(CryptoError::UnknownType(xtype)));
+        }
+
+        let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
+
+        let verkey = VerKey::from_bytes(&xtype, raw_verkey)?;
+        let sealed = signus.seal(verkey.as_bytes(), doc.as_bytes())?;
+        Ok(Base58::encode(&sealed))
+    }
+
+    /// Opens a message produced by `seal` using `my_did`'s own key pair.
+    pub fn seal_open(&self, my_did: &MyDid, doc: &str) -> Result<String, SignusError> {
+        if !self.crypto_types.contains_key(&my_did.crypto_type.as_str()) {
+            return Err(SignusError::CryptoError(CryptoError::UnknownType(my_did.crypto_type.clone())));
+        }
+
+        let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
+
+        let sign_key = SignKey::from_base58(&my_did.crypto_type, &my_did.sign_key)?;
+        let (_, raw_ver_key) = decode_key(&my_did.ver_key)?;
+        let ver_key = VerKey::from_bytes(&my_did.crypto_type, raw_ver_key)?;
+        let sealed = Base58::decode(doc)?;
+
+        let opened = signus.seal_open(sign_key.as_bytes(), ver_key.as_bytes(), &sealed)?;
+        Ok(Base58::encode(&opened))
+    }
 }
 
 #[cfg(test)]
@@ -153,7 +323,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: None,
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
 
         let res = service.create_my_did(&did_info);
@@ -169,7 +340,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: did.clone(),
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
 
         let res = service.create_my_did(&did_info);
@@ -187,7 +359,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: did.clone(),
             seed: None,
-            crypto_type: crypto_type
+            crypto_type: crypto_type,
+            self_describing_verkey: None
         };
 
         let res = service.create_my_did(&did_info);
@@ -203,12 +376,14 @@ mod tests {
         let did_info_with_seed = MyDidInfo {
             did: did.clone(),
             seed: seed,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
         let did_info_without_seed = MyDidInfo {
             did: did.clone(),
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
 
         let res_with_seed = service.create_my_did(&did_info_with_seed);
@@ -220,6 +395,73 @@ mod tests {
         assert_ne!(res_with_seed.unwrap().ver_key, res_without_seed.unwrap().ver_key)
     }
 
+    #[test]
+    fn create_my_did_defaults_to_legacy_unprefixed_verkey_for_backward_compatibility() {
+        let service = SignusService::new();
+
+        for crypto_type in &[None, Some("p256".to_string()), Some("secp256k1".to_string())] {
+            let did_info = MyDidInfo {
+                did: None,
+                seed: None,
+                crypto_type: crypto_type.clone(),
+                self_describing_verkey: None
+            };
+
+            let my_did = service.create_my_did(&did_info).unwrap();
+            let (decoded_type, _) = decode_key(&my_did.ver_key).unwrap();
+            assert_eq!(None, decoded_type, "expected an unprefixed verkey for crypto_type {:?}", crypto_type);
+        }
+    }
+
+    #[test]
+    fn create_my_did_self_describes_verkey_when_opted_in() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: Some("p256".to_string()),
+            self_describing_verkey: Some(true)
+        };
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+        let (decoded_type, _) = decode_key(&my_did.ver_key).unwrap();
+        assert_eq!(Some("p256".to_string()), decoded_type);
+    }
+
+    #[test]
+    fn create_my_did_with_wrong_length_seed_fails_instead_of_panicking_for_ed25519() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: Some("too short".to_string()),
+            crypto_type: None,
+            self_describing_verkey: None
+        };
+
+        let res = service.create_my_did(&did_info);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn create_my_did_accepts_arbitrary_length_seed_when_switching_crypto_type() {
+        let service = SignusService::new();
+        let seed = "too short".to_string();
+
+        for crypto_type in &["p256", "secp256k1"] {
+            let did_info = MyDidInfo {
+                did: None,
+                seed: Some(seed.clone()),
+                crypto_type: Some(crypto_type.to_string()),
+                self_describing_verkey: None
+            };
+
+            let res = service.create_my_did(&did_info);
+            assert!(res.is_ok(), "create_my_did failed for crypto_type '{}': {:?}", crypto_type, res.err());
+        }
+    }
+
     #[test]
     fn sign_works() {
         let service = SignusService::new();
@@ -227,7 +469,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: None,
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
         let msg = "some message";
 
@@ -246,7 +489,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: None,
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
         let msg = "some message";
 
@@ -278,7 +522,8 @@ mod tests {
         let did_info = MyDidInfo {
             did: None,
             seed: None,
-            crypto_type: None
+            crypto_type: None,
+            self_describing_verkey: None
         };
         let msg = "message";
 
@@ -302,4 +547,223 @@ mod tests {
 //        assert!(res.is_ok());
 //        assert_eq!(false, res.unwrap());
     }
+
+    #[test]
+    fn try_verify_with_truncated_verkey_fails_fast() {
+        let service = SignusService::new();
+
+        let their_did = TheirDid {
+            did: "sw2SA2jCbsiq2kfns".to_string(),
+            crypto_type: Some(DEFAULT_CRYPTO_TYPE.to_string()),
+            pk: None,
+            verkey: Base58::encode(&[0u8; 16])
+        };
+
+        let res = service.verify(&their_did, "message", &Base58::encode(&[0u8; 64]));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn seal_seal_open_works() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: None,
+            self_describing_verkey: None
+        };
+        let msg = "some anonymous message";
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        let their_did = TheirDid {
+            did: my_did.did.clone(),
+            crypto_type: Some(my_did.crypto_type.clone()),
+            pk: None,
+            verkey: my_did.ver_key.clone()
+        };
+
+        let sealed = service.seal(&their_did, msg);
+        assert!(sealed.is_ok());
+
+        let opened = service.seal_open(&my_did, &sealed.unwrap());
+        assert!(opened.is_ok());
+    }
+
+    #[test]
+    fn sign_verify_and_seal_seal_open_work_for_p256_and_secp256k1() {
+        for crypto_type in &["p256", "secp256k1"] {
+            let service = SignusService::new();
+
+            let did_info = MyDidInfo {
+                did: None,
+                seed: None,
+                crypto_type: Some(crypto_type.to_string()),
+                self_describing_verkey: None
+            };
+            let msg = "some message";
+
+            let my_did = service.create_my_did(&did_info).unwrap();
+
+            let their_did = TheirDid {
+                did: my_did.did.clone(),
+                crypto_type: Some(my_did.crypto_type.clone()),
+                pk: None,
+                verkey: my_did.ver_key.clone()
+            };
+
+            let signature = service.sign(&my_did, msg).unwrap();
+            let res = service.verify(&their_did, msg, &signature);
+            assert!(res.is_ok(), "verify failed for crypto_type '{}': {:?}", crypto_type, res);
+            assert!(res.unwrap());
+
+            let sealed = service.seal(&their_did, msg).unwrap();
+            let opened = service.seal_open(&my_did, &sealed);
+            assert!(opened.is_ok(), "seal_open failed for crypto_type '{}': {:?}", crypto_type, opened.err());
+            assert_eq!(msg.as_bytes().to_vec(), Base58::decode(&opened.unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn seal_returns_err_instead_of_panicking_for_malformed_p256_and_secp256k1_verkeys() {
+        for crypto_type in &["p256", "secp256k1"] {
+            let service = SignusService::new();
+
+            // Correctly sized (65-byte uncompressed SEC1 point) but not a point on the curve.
+            let bogus_verkey = VerKey::from_bytes(crypto_type, vec![0u8; 65]).unwrap();
+
+            let their_did = TheirDid {
+                did: "sw2SA2jCbsiq2kfns".to_string(),
+                crypto_type: Some(crypto_type.to_string()),
+                pk: None,
+                verkey: Base58::encode(bogus_verkey.as_bytes())
+            };
+
+            let res = service.seal(&their_did, "some message");
+            assert!(res.is_err(), "expected an error, not a panic, for crypto_type '{}'", crypto_type);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "enable-crypto-none")]
+    fn create_my_did_with_none_crypto_type_works_when_feature_enabled() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: Some("none".to_string()),
+            self_describing_verkey: None
+        };
+
+        let res = service.create_my_did(&did_info);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature = "enable-crypto-none"))]
+    fn create_my_did_with_none_crypto_type_fails_when_feature_disabled() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: Some("none".to_string()),
+            self_describing_verkey: None
+        };
+
+        let res = service.create_my_did(&did_info);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn sign_digest_verify_digest_works_with_default_algorithm() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: None,
+            self_describing_verkey: None
+        };
+        let msg = "some large document";
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        let signature = service.sign_digest(&my_did, msg, None);
+        assert!(signature.is_ok());
+        let signature = signature.unwrap();
+
+        let their_did = TheirDid {
+            did: my_did.did.clone(),
+            crypto_type: Some(my_did.crypto_type.clone()),
+            pk: None,
+            verkey: my_did.ver_key.clone()
+        };
+
+        let res = service.verify_digest(&their_did, msg, &signature);
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn sign_digest_verify_digest_works_with_sha256() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: None,
+            self_describing_verkey: None
+        };
+        let msg = "some large document";
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        let signature = service.sign_digest(&my_did, msg, Some(HashAlgorithm::Sha256)).unwrap();
+
+        let their_did = TheirDid {
+            did: my_did.did.clone(),
+            crypto_type: Some(my_did.crypto_type.clone()),
+            pk: None,
+            verkey: my_did.ver_key.clone()
+        };
+
+        let res = service.verify_digest(&their_did, msg, &signature);
+        assert!(res.is_ok());
+        assert!(res.unwrap());
+    }
+
+    #[test]
+    fn verify_digest_detects_tampered_hash_algorithm_tag() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo {
+            did: None,
+            seed: None,
+            crypto_type: None,
+            self_describing_verkey: None
+        };
+        let msg = "some large document";
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign_digest(&my_did, msg, Some(HashAlgorithm::Sha256)).unwrap();
+
+        // flip the leading hash-algorithm tag byte so the recomputed digest no longer matches
+        let mut tagged = Base58::decode(&signature).unwrap();
+        tagged[0] = HashAlgorithm::Sha512.tag();
+        let tampered_signature = Base58::encode(&tagged);
+
+        let their_did = TheirDid {
+            did: my_did.did.clone(),
+            crypto_type: Some(my_did.crypto_type.clone()),
+            pk: None,
+            verkey: my_did.ver_key.clone()
+        };
+
+        let res = service.verify_digest(&their_did, msg, &tampered_signature);
+        assert!(res.is_ok());
+        assert!(!res.unwrap());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,147 @@
+use services::signus::CryptoType;
+use errors::crypto::CryptoError;
+
+const KEY_LEN: usize = 32;
+const SIGNATURE_LEN: usize = 64;
+const NONCE_LEN: usize = 12;
+
+/// A no-op "none" crypto system: keys are deterministic filler bytes, signatures are a trivial
+/// checksum, and encryption is the identity transform. It exists purely to exercise
+/// `SignusService`'s crypto-type dispatch and error handling in tests/benchmarks without paying
+/// for real cryptography.
+pub struct NoneSignus {}
+
+impl NoneSignus {
+    pub fn new() -> NoneSignus {
+        NoneSignus {}
+    }
+
+    fn deterministic_bytes(seed: Option<&[u8]>, len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        if let Some(seed) = seed {
+            if !seed.is_empty() {
+                for (i, b) in bytes.iter_mut().enumerate() {
+                    *b = seed[i % seed.len()];
+                }
+            }
+        }
+        bytes
+    }
+
+    fn tag(key: &[u8], doc: &[u8]) -> Vec<u8> {
+        let mut tag = vec![0u8; SIGNATURE_LEN];
+        for (i, byte) in key.iter().chain(doc.iter()).enumerate() {
+            tag[i % SIGNATURE_LEN] ^= *byte;
+        }
+        tag
+    }
+}
+
+impl CryptoType for NoneSignus {
+    fn create_key_pair(&self) -> (Vec<u8>, Vec<u8>) {
+        (NoneSignus::deterministic_bytes(None, KEY_LEN), NoneSignus::deterministic_bytes(None, KEY_LEN))
+    }
+
+    fn encrypt(&self, _private_key: &[u8], _public_key: &[u8], doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(doc.to_vec())
+    }
+
+    fn decrypt(&self, _private_key: &[u8], _public_key: &[u8], doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(doc.to_vec())
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        vec![0u8; NONCE_LEN]
+    }
+
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> (Vec<u8>, Vec<u8>) {
+        (NoneSignus::deterministic_bytes(seed, KEY_LEN), NoneSignus::deterministic_bytes(seed, KEY_LEN))
+    }
+
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(NoneSignus::tag(private_key, doc))
+    }
+
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> bool {
+        NoneSignus::tag(public_key, doc) == signature
+    }
+
+    fn seal(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut sealed = public_key.to_vec();
+        sealed.extend_from_slice(doc);
+        Ok(sealed)
+    }
+
+    fn seal_open(&self, _private_key: &[u8], public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if doc.len() < public_key.len() || &doc[..public_key.len()] != public_key {
+            return Err(CryptoError::InvalidStructure("Sealed message does not match recipient key".to_string()));
+        }
+        Ok(doc[public_key.len()..].to_vec())
+    }
+
+    fn precompute(&self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let mut shared = private_key.to_vec();
+        shared.extend_from_slice(public_key);
+        Ok(shared)
+    }
+
+    fn encrypt_precomputed(&self, _shared_secret: &[u8], doc: &[u8], _nonce: &[u8]) -> Vec<u8> {
+        doc.to_vec()
+    }
+
+    fn decrypt_precomputed(&self, _shared_secret: &[u8], doc: &[u8], _nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(doc.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_sign_verify_round_trips() {
+        let signus = NoneSignus::new();
+        let (vk, sk) = signus.create_key_pair_for_signature(None);
+        let doc = b"some message";
+
+        let signature = signus.sign(&sk, doc).unwrap();
+        assert!(signus.verify(&vk, doc, &signature));
+    }
+
+    #[test]
+    fn none_create_key_pair_for_signature_with_seed_is_deterministic() {
+        let signus = NoneSignus::new();
+        let seed = b"00000000000000000000000000seed";
+
+        let (vk1, sk1) = signus.create_key_pair_for_signature(Some(seed));
+        let (vk2, sk2) = signus.create_key_pair_for_signature(Some(seed));
+
+        assert_eq!(vk1, vk2);
+        assert_eq!(sk1, sk2);
+    }
+
+    #[test]
+    fn none_encrypt_decrypt_round_trips() {
+        let signus = NoneSignus::new();
+        let (pk, sk) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let encrypted = signus.encrypt(&sk, &pk, doc, &nonce).unwrap();
+        let decrypted = signus.decrypt(&sk, &pk, &encrypted, &nonce).unwrap();
+
+        assert_eq!(doc.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn none_seal_seal_open_round_trips() {
+        let signus = NoneSignus::new();
+        let (vk, sk) = signus.create_key_pair_for_signature(None);
+        let doc = b"some anonymous message";
+
+        let sealed = signus.seal(&vk, doc).unwrap();
+        let opened = signus.seal_open(&sk, &vk, &sealed).unwrap();
+
+        assert_eq!(doc.to_vec(), opened);
+    }
+}
@@ -0,0 +1,325 @@
+extern crate p256;
+extern crate ecdsa;
+extern crate elliptic_curve;
+extern crate rand;
+extern crate sha2;
+extern crate hkdf;
+extern crate blake2;
+extern crate chacha20poly1305;
+
+use services::signus::CryptoType;
+use errors::crypto::CryptoError;
+
+use self::p256::{NistP256, SecretKey, PublicKey};
+use self::elliptic_curve::NonZeroScalar;
+use self::ecdsa::{SigningKey, VerifyingKey};
+use self::ecdsa::signature::{Signer, Verifier};
+use self::rand::rngs::OsRng;
+use self::rand::RngCore;
+use self::sha2::Sha256;
+use self::hkdf::Hkdf;
+use self::blake2::{Blake2s256, Digest};
+use self::chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use self::chacha20poly1305::aead::{Aead, NewAead};
+
+const NONCE_LEN: usize = 12;
+
+pub struct P256Signus {}
+
+impl P256Signus {
+    pub fn new() -> P256Signus {
+        P256Signus {}
+    }
+
+    fn expand_shared_secret(raw_secret: &[u8], info: &[u8]) -> Result<[u8; 32], CryptoError> {
+        let hk = Hkdf::<Sha256>::new(None, raw_secret);
+        let mut okm = [0u8; 32];
+        hk.expand(info, &mut okm)
+            .map_err(|_| CryptoError::BackendError("HKDF expand failed".to_string()))?;
+        Ok(okm)
+    }
+
+    fn derive_aead_key(private_key: &[u8], public_key: &[u8]) -> Result<[u8; 32], CryptoError> {
+        let sk = SecretKey::from_be_bytes(private_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 secret key".to_string()))?;
+        let pk = PublicKey::from_sec1_bytes(public_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 public key".to_string()))?;
+
+        let shared = elliptic_curve::ecdh::diffie_hellman(sk.to_nonzero_scalar(), pk.as_affine());
+        P256Signus::expand_shared_secret(shared.raw_secret_bytes().as_slice(), b"indy-signus-p256-aead")
+    }
+
+    /// Derives a blinding scalar from `verkey` (the long-term signing identity key), computable by
+    /// both sides of a `seal`/`seal_open` exchange: the sealer already has the recipient's verkey,
+    /// and the opener can recompute it from their own key pair. Multiplying each side's own DH
+    /// scalar by this shared blinding factor before running ECDH (see `blind_scalar`) derives a
+    /// key-agreement secret that's distinct from the raw ECDSA signing scalar, without requiring
+    /// either party to know the other's private key -- the NIST curves have no canonical
+    /// sign-to-agreement key conversion analogous to ed25519's pk_to_curve25519/sk_to_curve25519.
+    fn seal_blinding_scalar(verkey: &[u8]) -> Result<NonZeroScalar<NistP256>, CryptoError> {
+        let blinding_bytes = P256Signus::expand_shared_secret(verkey, b"indy-signus-p256-seal-agreement-blind")?;
+        let blinding_key = SecretKey::from_be_bytes(&blinding_bytes)
+            .map_err(|_| CryptoError::BackendError("Derived blinding scalar was invalid".to_string()))?;
+        Ok(blinding_key.to_nonzero_scalar())
+    }
+
+    /// Multiplies two nonzero scalars. The product of two nonzero elements of a prime field is
+    /// always itself nonzero, so the `NonZeroScalar` re-wrap below cannot fail.
+    fn blind_scalar(scalar: NonZeroScalar<NistP256>, blinding: NonZeroScalar<NistP256>) -> NonZeroScalar<NistP256> {
+        let product = *scalar * *blinding;
+        Option::from(NonZeroScalar::new(product))
+            .expect("product of two nonzero scalars in a prime field is always nonzero")
+    }
+
+    /// Derives the seal nonce from the first 12 bytes of `blake2s(ephemeral_pk || recipient_pk)`,
+    /// following the libsodium sealed-box construction so the recipient can recompute it without a
+    /// transmitted nonce.
+    fn seal_nonce(ephemeral_pk: &[u8], recipient_pk: &[u8]) -> [u8; NONCE_LEN] {
+        let mut hasher = Blake2s256::new();
+        hasher.update(ephemeral_pk);
+        hasher.update(recipient_pk);
+        let digest = hasher.finalize();
+
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce.copy_from_slice(&digest[..NONCE_LEN]);
+        nonce
+    }
+}
+
+impl CryptoType for P256Signus {
+    fn create_key_pair(&self) -> (Vec<u8>, Vec<u8>) {
+        let sk = SecretKey::random(&mut OsRng);
+        let pk = sk.public_key();
+        (pk.to_encoded_point(false).as_bytes().to_vec(), sk.to_be_bytes().to_vec())
+    }
+
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let key_bytes = P256Signus::derive_aead_key(private_key, public_key)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        cipher.encrypt(Nonce::from_slice(nonce), doc).map_err(|_| CryptoError::BackendError("Unable to encrypt".to_string()))
+    }
+
+    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let key_bytes = P256Signus::derive_aead_key(private_key, public_key)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        cipher.decrypt(Nonce::from_slice(nonce), doc)
+            .map_err(|_| CryptoError::BackendError("Unable to decrypt".to_string()))
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce);
+        nonce.to_vec()
+    }
+
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> (Vec<u8>, Vec<u8>) {
+        let sk = match seed {
+            Some(seed) => {
+                let hk = Hkdf::<Sha256>::new(None, seed);
+                let mut okm = [0u8; 32];
+                hk.expand(b"indy-signus-p256-signing-key", &mut okm).expect("HKDF expand failed");
+                SigningKey::from_bytes(&okm).expect("deterministic P-256 key from seed")
+            }
+            None => SigningKey::random(&mut OsRng)
+        };
+        let vk = VerifyingKey::from(&sk);
+        (vk.to_encoded_point(false).as_bytes().to_vec(), sk.to_bytes().to_vec())
+    }
+
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let sk = SigningKey::from_bytes(private_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 signing key".to_string()))?;
+        let sig: ecdsa::Signature<NistP256> = sk.sign(doc);
+        Ok(sig.as_ref().to_vec())
+    }
+
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> bool {
+        let vk = match VerifyingKey::from_sec1_bytes(public_key) {
+            Ok(vk) => vk,
+            Err(_) => return false
+        };
+        let sig = match ecdsa::Signature::<NistP256>::from_bytes(signature) {
+            Ok(sig) => sig,
+            Err(_) => return false
+        };
+        vk.verify(doc, &sig).is_ok()
+    }
+
+    fn seal(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let ephemeral_sk = SecretKey::random(&mut OsRng);
+        let ephemeral_pk = ephemeral_sk.public_key().to_encoded_point(false).as_bytes().to_vec();
+
+        let recipient_pk = PublicKey::from_sec1_bytes(public_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 public key".to_string()))?;
+        let blinding = P256Signus::seal_blinding_scalar(public_key)?;
+        let blinded_ephemeral_scalar = P256Signus::blind_scalar(ephemeral_sk.to_nonzero_scalar(), blinding);
+
+        let shared = elliptic_curve::ecdh::diffie_hellman(blinded_ephemeral_scalar, recipient_pk.as_affine());
+        let key_bytes = P256Signus::expand_shared_secret(shared.raw_secret_bytes().as_slice(), b"indy-signus-p256-seal-aead")?;
+        let nonce = P256Signus::seal_nonce(&ephemeral_pk, public_key);
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce), doc)
+            .map_err(|_| CryptoError::BackendError("Unable to encrypt".to_string()))?;
+
+        let mut sealed = ephemeral_pk;
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    fn seal_open(&self, private_key: &[u8], public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let ephemeral_pk_len = PublicKey::from_sec1_bytes(public_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 public key".to_string()))?
+            .to_encoded_point(false).as_bytes().len();
+
+        if doc.len() < ephemeral_pk_len {
+            return Err(CryptoError::InvalidStructure("Sealed message is too short".to_string()));
+        }
+
+        let (ephemeral_pk, ciphertext) = doc.split_at(ephemeral_pk_len);
+        let ephemeral_pub_key = PublicKey::from_sec1_bytes(ephemeral_pk)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid ephemeral P-256 public key".to_string()))?;
+
+        let my_sign_key = SecretKey::from_be_bytes(private_key)
+            .map_err(|_| CryptoError::InvalidStructure("Invalid P-256 secret key".to_string()))?;
+        let blinding = P256Signus::seal_blinding_scalar(public_key)?;
+        let blinded_signkey_scalar = P256Signus::blind_scalar(my_sign_key.to_nonzero_scalar(), blinding);
+
+        let shared = elliptic_curve::ecdh::diffie_hellman(blinded_signkey_scalar, ephemeral_pub_key.as_affine());
+        let key_bytes = P256Signus::expand_shared_secret(shared.raw_secret_bytes().as_slice(), b"indy-signus-p256-seal-aead")?;
+        let nonce = P256Signus::seal_nonce(ephemeral_pk, public_key);
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        cipher.decrypt(Nonce::from_slice(&nonce), ciphertext)
+            .map_err(|_| CryptoError::BackendError("Unable to open sealed box".to_string()))
+    }
+
+    fn precompute(&self, private_key: &[u8], public_key: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(P256Signus::derive_aead_key(private_key, public_key)?.to_vec())
+    }
+
+    fn encrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Vec<u8> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(shared_secret));
+        cipher.encrypt(Nonce::from_slice(nonce), doc).expect("encryption failure!")
+    }
+
+    fn decrypt_precomputed(&self, shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(shared_secret));
+        cipher.decrypt(Nonce::from_slice(nonce), doc)
+            .map_err(|_| CryptoError::BackendError("Unable to decrypt".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn p256_sign_verify_works() {
+        let signus = P256Signus::new();
+        let (vk, sk) = signus.create_key_pair_for_signature(None);
+        let doc = b"some message";
+
+        let signature = signus.sign(&sk, doc).unwrap();
+        assert!(signus.verify(&vk, doc, &signature));
+    }
+
+    #[test]
+    fn p256_create_key_pair_for_signature_with_seed_is_deterministic() {
+        let signus = P256Signus::new();
+        let seed = b"00000000000000000000000000seed";
+
+        let (vk1, sk1) = signus.create_key_pair_for_signature(Some(seed));
+        let (vk2, sk2) = signus.create_key_pair_for_signature(Some(seed));
+
+        assert_eq!(vk1, vk2);
+        assert_eq!(sk1, sk2);
+    }
+
+    #[test]
+    fn p256_encrypt_decrypt_works() {
+        let signus = P256Signus::new();
+        let (pk1, sk1) = signus.create_key_pair();
+        let (pk2, sk2) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let encrypted = signus.encrypt(&sk1, &pk2, doc, &nonce).unwrap();
+        let decrypted = signus.decrypt(&sk2, &pk1, &encrypted, &nonce).unwrap();
+
+        assert_eq!(doc.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn p256_precomputed_encrypt_is_compatible_with_plain_decrypt() {
+        let signus = P256Signus::new();
+        let (pk1, sk1) = signus.create_key_pair();
+        let (pk2, sk2) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let shared_secret = signus.precompute(&sk1, &pk2).unwrap();
+        let encrypted = signus.encrypt_precomputed(&shared_secret, doc, &nonce);
+        let decrypted = signus.decrypt(&sk2, &pk1, &encrypted, &nonce).unwrap();
+
+        assert_eq!(doc.to_vec(), decrypted);
+    }
+
+    #[test]
+    fn p256_seal_seal_open_works() {
+        let signus = P256Signus::new();
+        let (pk, sk) = signus.create_key_pair();
+        let doc = b"some anonymous message";
+
+        let sealed = signus.seal(&pk, doc).unwrap();
+        let opened = signus.seal_open(&sk, &pk, &sealed).unwrap();
+
+        assert_eq!(doc.to_vec(), opened);
+    }
+
+    #[test]
+    fn p256_seal_open_fails_for_wrong_recipient() {
+        let signus = P256Signus::new();
+        let (pk, _) = signus.create_key_pair();
+        let (_, other_sk) = signus.create_key_pair();
+        let doc = b"some anonymous message";
+
+        let sealed = signus.seal(&pk, doc).unwrap();
+
+        assert!(signus.seal_open(&other_sk, &pk, &sealed).is_err());
+    }
+
+    #[test]
+    fn p256_encrypt_returns_err_instead_of_panicking_for_invalid_peer_public_key() {
+        let signus = P256Signus::new();
+        let (_, sk1) = signus.create_key_pair();
+        let nonce = signus.gen_nonce();
+        let doc = b"some message";
+
+        let bogus_public_key = vec![0u8; 65];
+        assert!(signus.encrypt(&sk1, &bogus_public_key, doc, &nonce).is_err());
+    }
+
+    #[test]
+    fn p256_seal_returns_err_instead_of_panicking_for_invalid_recipient_verkey() {
+        let signus = P256Signus::new();
+        let doc = b"some anonymous message";
+
+        let bogus_verkey = vec![0u8; 65];
+        assert!(signus.seal(&bogus_verkey, doc).is_err());
+    }
+
+    #[test]
+    fn p256_seal_blinds_the_signing_scalar_instead_of_reusing_it_directly() {
+        let signus = P256Signus::new();
+        let (verkey, sign_key) = signus.create_key_pair_for_signature(None);
+
+        let sign_key = SecretKey::from_be_bytes(&sign_key).unwrap();
+        let blinding = P256Signus::seal_blinding_scalar(&verkey).unwrap();
+        let blinded = P256Signus::blind_scalar(sign_key.to_nonzero_scalar(), blinding);
+
+        // `seal`/`seal_open` must use a scalar distinct from the raw ECDSA signing scalar for
+        // key agreement, rather than feeding it directly into ECDH the way `precompute` does.
+        assert_ne!(*sign_key.to_nonzero_scalar(), *blinded);
+    }
+}
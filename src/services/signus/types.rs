@@ -0,0 +1,187 @@
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MyDidInfo {
+    pub did: Option<String>,
+    pub seed: Option<String>,
+    pub crypto_type: Option<String>,
+    /// Opt-in: self-describe `ver_key` with its multicodec prefix (see `services::signus::encoding`)
+    /// instead of the legacy raw/unprefixed encoding. Defaults to `false` (legacy encoding) so
+    /// existing callers that expect a raw verkey aren't silently broken by a format change.
+    pub self_describing_verkey: Option<bool>
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MyDid {
+    pub did: String,
+    pub crypto_type: String,
+    pub pk: String,
+    pub secret_key: String,
+    pub ver_key: String,
+    pub sign_key: String
+}
+
+impl MyDid {
+    pub fn new(did: String, crypto_type: String, pk: String, secret_key: String,
+               ver_key: String, sign_key: String) -> MyDid {
+        MyDid {
+            did: did,
+            crypto_type: crypto_type,
+            pk: pk,
+            secret_key: secret_key,
+            ver_key: ver_key,
+            sign_key: sign_key
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TheirDid {
+    pub did: String,
+    pub crypto_type: Option<String>,
+    pub pk: Option<String>,
+    pub verkey: String
+}
+
+use errors::crypto::CryptoError;
+use errors::signus::SignusError;
+use utils::crypto::base58::Base58;
+
+/// Expected raw byte length of a verkey (verifying/public signing key) for `crypto_type`, or
+/// `None` if the crypto type is unknown and length can't be validated. ed25519 verkeys are raw
+/// 32-byte Edwards points; p256/secp256k1 verkeys are uncompressed SEC1 points, as produced by
+/// `create_key_pair_for_signature`.
+fn expected_verkey_len(crypto_type: &str) -> Option<usize> {
+    match crypto_type {
+        "ed25519" => Some(32),
+        "p256" => Some(65),
+        "secp256k1" => Some(65),
+        _ => None
+    }
+}
+
+/// Expected raw byte length of a sign_key for `crypto_type`: libsodium's 64-byte expanded secret
+/// key (seed followed by verkey) for ed25519, the 32-byte private signing scalar for p256/secp256k1.
+fn expected_sign_key_len(crypto_type: &str) -> Option<usize> {
+    match crypto_type {
+        "ed25519" => Some(64),
+        "p256" => Some(32),
+        "secp256k1" => Some(32),
+        _ => None
+    }
+}
+
+/// Expected raw byte length of a detached signature for `crypto_type`.
+fn expected_signature_len(crypto_type: &str) -> Option<usize> {
+    match crypto_type {
+        "ed25519" => Some(64),
+        "p256" => Some(64),
+        "secp256k1" => Some(64),
+        _ => None
+    }
+}
+
+/// Expected raw byte length of an encryption public key for `crypto_type` (an uncompressed SEC1
+/// point for the NIST/secp256k1 curves, a raw Curve25519 point for ed25519).
+fn expected_encryption_public_key_len(crypto_type: &str) -> Option<usize> {
+    match crypto_type {
+        "ed25519" => Some(32),
+        "p256" => Some(65),
+        "secp256k1" => Some(65),
+        _ => None
+    }
+}
+
+/// Expected raw byte length of an encryption secret key (scalar) for `crypto_type`.
+fn expected_encryption_secret_key_len(crypto_type: &str) -> Option<usize> {
+    match crypto_type {
+        "ed25519" => Some(32),
+        "p256" => Some(32),
+        "secp256k1" => Some(32),
+        _ => None
+    }
+}
+
+fn validate_len(label: &str, crypto_type: &str, expected: Option<usize>, actual: usize) -> Result<(), SignusError> {
+    match expected {
+        Some(expected) if expected != actual =>
+            Err(SignusError::CryptoError(CryptoError::InvalidStructure(
+                format!("Invalid {} length for crypto type '{}': expected {} bytes, got {}",
+                        label, crypto_type, expected, actual)))),
+        _ => Ok(())
+    }
+}
+
+macro_rules! validated_key_type {
+    ($name:ident, $label:expr, $expected_len:expr) => {
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name(Vec<u8>);
+
+        impl $name {
+            pub fn from_bytes(crypto_type: &str, bytes: Vec<u8>) -> Result<$name, SignusError> {
+                validate_len($label, crypto_type, $expected_len(crypto_type), bytes.len())?;
+                Ok($name(bytes))
+            }
+
+            pub fn from_base58(crypto_type: &str, encoded: &str) -> Result<$name, SignusError> {
+                $name::from_bytes(crypto_type, Base58::decode(encoded)?)
+            }
+
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.0
+            }
+        }
+    }
+}
+
+validated_key_type!(VerKey, "verkey", expected_verkey_len);
+validated_key_type!(SignKey, "sign_key", expected_sign_key_len);
+validated_key_type!(Signature, "signature", expected_signature_len);
+validated_key_type!(PublicKey, "public_key", expected_encryption_public_key_len);
+validated_key_type!(SecretKey, "secret_key", expected_encryption_secret_key_len);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verkey_from_bytes_rejects_wrong_length() {
+        assert!(VerKey::from_bytes("ed25519", vec![0u8; 31]).is_err());
+        assert!(VerKey::from_bytes("ed25519", vec![0u8; 32]).is_ok());
+    }
+
+    #[test]
+    fn verkey_from_bytes_expects_uncompressed_ec_point_for_p256_and_secp256k1() {
+        assert!(VerKey::from_bytes("p256", vec![0u8; 32]).is_err());
+        assert!(VerKey::from_bytes("p256", vec![0u8; 65]).is_ok());
+        assert!(VerKey::from_bytes("secp256k1", vec![0u8; 32]).is_err());
+        assert!(VerKey::from_bytes("secp256k1", vec![0u8; 65]).is_ok());
+    }
+
+    #[test]
+    fn sign_key_from_bytes_rejects_wrong_length() {
+        assert!(SignKey::from_bytes("ed25519", vec![0u8; 16]).is_err());
+        assert!(SignKey::from_bytes("ed25519", vec![0u8; 64]).is_ok());
+    }
+
+    #[test]
+    fn signature_from_bytes_rejects_wrong_length() {
+        assert!(Signature::from_bytes("ed25519", vec![0u8; 63]).is_err());
+        assert!(Signature::from_bytes("ed25519", vec![0u8; 64]).is_ok());
+    }
+
+    #[test]
+    fn public_key_from_bytes_rejects_wrong_length() {
+        assert!(PublicKey::from_bytes("p256", vec![0u8; 64]).is_err());
+        assert!(PublicKey::from_bytes("p256", vec![0u8; 65]).is_ok());
+    }
+
+    #[test]
+    fn secret_key_from_bytes_rejects_wrong_length() {
+        assert!(SecretKey::from_bytes("secp256k1", vec![0u8; 31]).is_err());
+        assert!(SecretKey::from_bytes("secp256k1", vec![0u8; 32]).is_ok());
+    }
+
+    #[test]
+    fn validated_key_type_accepts_unknown_crypto_type_unchecked() {
+        assert!(VerKey::from_bytes("unknown", vec![0u8; 3]).is_ok());
+    }
+}